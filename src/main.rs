@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
     error::Error,
     fs,
@@ -7,15 +7,24 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use glob::Pattern;
 use log::{debug, warn};
 use pathdiff::diff_paths;
+use semver::{Op, Version, VersionReq};
+use similar::TextDiff;
 use toml_edit::{value, DocumentMut, Formatted, InlineTable, Item, Table, TomlError, Value};
 
 /// Represents the “source” of a dependency.
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum DepSource {
-    /// A version dependency (e.g. `"0.8.3"` or `{ version = "0.8.3", ... }`)
-    Version(String),
+    /// A version dependency (e.g. `"0.8.3"` or `{ version = "0.8.3", ... }`), optionally
+    /// routed through an alternative registry via `registry` (a name from cargo config) or
+    /// `registry-index` (a bare index URL).
+    Version {
+        req: String,
+        registry: Option<String>,
+        registry_index: Option<String>,
+    },
     /// A git dependency with a URL and optionally branch/rev/tag.
     Git {
         url: String,
@@ -31,7 +40,20 @@ enum DepSource {
 impl std::fmt::Display for DepSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            DepSource::Version(v) => write!(f, "version: {}", v),
+            DepSource::Version {
+                req,
+                registry,
+                registry_index,
+            } => {
+                write!(f, "version: {}", req)?;
+                if let Some(registry) = registry {
+                    write!(f, ", registry: {}", registry)?;
+                }
+                if let Some(registry_index) = registry_index {
+                    write!(f, ", registry-index: {}", registry_index)?;
+                }
+                Ok(())
+            }
             DepSource::Git {
                 url,
                 branch,
@@ -56,6 +78,119 @@ impl std::fmt::Display for DepSource {
     }
 }
 
+/// Which dependency table a specification lives in, mirroring cargo's own `DepKind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DepKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl DepKind {
+    const ALL: [DepKind; 3] = [DepKind::Normal, DepKind::Dev, DepKind::Build];
+
+    /// The TOML table key for this kind, e.g. `"dev-dependencies"`.
+    fn table_key(self) -> &'static str {
+        match self {
+            DepKind::Normal => "dependencies",
+            DepKind::Dev => "dev-dependencies",
+            DepKind::Build => "build-dependencies",
+        }
+    }
+}
+
+/// Identifies one dependency table in a manifest: its `DepKind`, plus — for target-scoped
+/// tables like `[target.'cfg(unix)'.dependencies]` — the cfg predicate it's scoped under.
+/// A dependency occurrence is keyed by `(DepTableKey, name)` rather than just `name` so that,
+/// say, a crate used as both a normal and a dev-dependency isn't silently merged into one
+/// occurrence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DepTableKey {
+    target_cfg: Option<String>,
+    kind: DepKind,
+}
+
+impl std::fmt::Display for DepTableKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.target_cfg {
+            Some(cfg) => write!(f, "target.'{}'.{}", cfg, self.kind.table_key()),
+            None => write!(f, "{}", self.kind.table_key()),
+        }
+    }
+}
+
+/// Look up a dependency table (immutably) by its `DepTableKey`, whether it's a top-level
+/// table or nested under `[target.'cfg(...)']`.
+fn get_dep_table<'a>(doc: &'a DocumentMut, key: &DepTableKey) -> Option<&'a Table> {
+    match &key.target_cfg {
+        None => doc.get(key.kind.table_key()).and_then(Item::as_table),
+        Some(cfg) => doc
+            .get("target")
+            .and_then(Item::as_table)
+            .and_then(|t| t.get(cfg.as_str()))
+            .and_then(Item::as_table)
+            .and_then(|t| t.get(key.kind.table_key()))
+            .and_then(Item::as_table),
+    }
+}
+
+/// Mutable counterpart of `get_dep_table`.
+fn get_dep_table_mut<'a>(doc: &'a mut DocumentMut, key: &DepTableKey) -> Option<&'a mut Table> {
+    match &key.target_cfg {
+        None => doc
+            .get_mut(key.kind.table_key())
+            .and_then(Item::as_table_mut),
+        Some(cfg) => doc
+            .get_mut("target")
+            .and_then(Item::as_table_mut)
+            .and_then(|t| t.get_mut(cfg.as_str()))
+            .and_then(Item::as_table_mut)
+            .and_then(|t| t.get_mut(key.kind.table_key()))
+            .and_then(Item::as_table_mut),
+    }
+}
+
+/// Enumerate every dependency table present in a manifest: the three top-level tables
+/// (`[dependencies]`, `[dev-dependencies]`, `[build-dependencies]`) plus their target-scoped
+/// variants under `[target.'cfg(...)'.*]`.
+fn discover_dep_tables(doc: &DocumentMut) -> Vec<DepTableKey> {
+    let mut keys = Vec::new();
+    for kind in DepKind::ALL {
+        let key = DepTableKey {
+            target_cfg: None,
+            kind,
+        };
+        if get_dep_table(doc, &key).is_some() {
+            keys.push(key);
+        }
+    }
+    if let Some(target_table) = doc.get("target").and_then(Item::as_table) {
+        let cfgs: Vec<String> = target_table.iter().map(|(cfg, _)| cfg.to_string()).collect();
+        for cfg in cfgs {
+            for kind in DepKind::ALL {
+                let key = DepTableKey {
+                    target_cfg: Some(cfg.clone()),
+                    kind,
+                };
+                if get_dep_table(doc, &key).is_some() {
+                    keys.push(key);
+                }
+            }
+        }
+    }
+    keys
+}
+
+/// Read a dependency's `package` rename, if any (e.g. `foo = { package = "actual-crate", ... }`,
+/// mirroring the `rename` field on cargo's own `Dependency`). Returns `None` for bare string
+/// dependencies, which can't rename.
+fn dep_package_rename(item: &Item) -> Option<String> {
+    item.as_table_like()?
+        .get("package")
+        .and_then(|v| v.as_value().and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
 /// Given a dependency item from a Cargo.toml, compute its source information.
 /// For local path dependencies, compute the path relative to the workspace root.
 /// `cargo_toml_path` is the path to the sub‑crate’s Cargo.toml.
@@ -69,7 +204,11 @@ fn compute_dep_source(
     if let Some(val) = item.as_value() {
         if let Some(s) = val.as_str() {
             debug!("Found bare string dependency source: {}", s);
-            return Some(DepSource::Version(s.to_string()));
+            return Some(DepSource::Version {
+                req: s.to_string(),
+                registry: None,
+                registry_index: None,
+            });
         }
     }
     // If the item is a table:
@@ -120,8 +259,23 @@ fn compute_dep_source(
         } else if let Some(version_item) = table.get("version") {
             // Otherwise, if there is a version key, use that.
             if let Some(version_str) = version_item.as_str() {
-                debug!("Found version dependency: {}", version_str);
-                return Some(DepSource::Version(version_str.to_string()));
+                let registry = table
+                    .get("registry")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                let registry_index = table
+                    .get("registry-index")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                debug!(
+                    "Found version dependency: {} (registry: {:?}, registry-index: {:?})",
+                    version_str, registry, registry_index
+                );
+                return Some(DepSource::Version {
+                    req: version_str.to_string(),
+                    registry,
+                    registry_index,
+                });
             }
         } else if let Some(workspace_item) = table.get("workspace") {
             if let Some(workspace_bool) = workspace_item.as_bool() {
@@ -137,13 +291,44 @@ fn compute_dep_source(
     None
 }
 
-/// Build the workspace dependency item from a chosen dependency source.
-/// The returned table will contain only the source information.
-fn build_workspace_dep(dep_source: &DepSource) -> Item {
+/// Build the workspace dependency item from a chosen dependency source, optionally
+/// overlaying a unified `features`/`default-features` set (see `--unify-features`).
+/// With no unified attributes the returned table contains only the source information,
+/// matching the tool's pre-existing behavior.
+///
+/// `path_base` is `Some((base_name, remainder))` when `--path-base` is active and
+/// `dep_source` is a `DepSource::Path`: instead of inlining the full path, the entry is
+/// written as `{ base = "<base_name>", path = "<remainder>" }` (RFC 3529).
+fn build_workspace_dep(
+    dep_source: &DepSource,
+    unified_features: &[String],
+    unified_default_features: Option<bool>,
+    path_base: Option<(&str, &str)>,
+) -> Item {
     let mut table = InlineTable::new();
     match dep_source {
-        DepSource::Version(v) => {
-            return Item::Value(Value::String(Formatted::new(v.clone())));
+        DepSource::Version {
+            req,
+            registry,
+            registry_index,
+        } => {
+            if unified_features.is_empty()
+                && unified_default_features.is_none()
+                && registry.is_none()
+                && registry_index.is_none()
+            {
+                return Item::Value(Value::String(Formatted::new(req.clone())));
+            }
+            table.insert("version", Value::String(Formatted::new(req.clone())));
+            if let Some(registry) = registry {
+                table.insert("registry", Value::String(Formatted::new(registry.clone())));
+            }
+            if let Some(registry_index) = registry_index {
+                table.insert(
+                    "registry-index",
+                    Value::String(Formatted::new(registry_index.clone())),
+                );
+            }
         }
         DepSource::Git {
             url,
@@ -163,18 +348,39 @@ fn build_workspace_dep(dep_source: &DepSource) -> Item {
             }
         }
         DepSource::Path(rel_path) => {
-            table.insert("path", Value::String(Formatted::new(rel_path.clone())));
+            if let Some((base_name, remainder)) = path_base {
+                table.insert("base", Value::String(Formatted::new(base_name.to_string())));
+                table.insert("path", Value::String(Formatted::new(remainder.to_string())));
+            } else {
+                table.insert("path", Value::String(Formatted::new(rel_path.clone())));
+            }
         }
         DepSource::Workspace => {
             panic!("Workspace source should not be used as a workspace dependency");
         }
     }
 
+    if !unified_features.is_empty() {
+        let features = unified_features
+            .iter()
+            .map(|f| Value::String(Formatted::new(f.clone())));
+        table.insert(
+            "features",
+            Value::Array(toml_edit::Array::from_iter(features)),
+        );
+    }
+    if let Some(default_features) = unified_default_features {
+        table.insert(
+            "default-features",
+            Value::Boolean(Formatted::new(default_features)),
+        );
+    }
+
     debug!("Building workspace dependency: {}", dep_source);
     Item::Value(Value::InlineTable(table))
 }
 
-static KEYS_TO_IGNORE: [&str; 7] = [
+static KEYS_TO_IGNORE: [&str; 10] = [
     "version",
     "git",
     "branch",
@@ -182,12 +388,113 @@ static KEYS_TO_IGNORE: [&str; 7] = [
     "tag",
     "path",
     "workspace",
+    "package",
+    "registry",
+    "registry-index",
 ];
 
+/// The `features` / `default-features` attributes attached to a single dependency occurrence,
+/// independent of where its source comes from. Mirrors the subset of cargo's own `Dependency`
+/// struct that `--unify-features` cares about.
+#[derive(Debug, Clone, Default)]
+struct DepAttributes {
+    features: Vec<String>,
+    default_features: Option<bool>,
+}
+
+/// Read the `features` and `default-features` keys off a dependency item. Bare string
+/// dependencies (`foo = "1.0"`) carry neither. `optional` is left untouched wherever it
+/// appears, since cargo does not allow it to be inherited from a workspace dependency.
+fn extract_dep_attributes(item: &Item) -> DepAttributes {
+    let mut attrs = DepAttributes::default();
+    let Some(table_like) = item.as_table_like() else {
+        return attrs;
+    };
+    if let Some(features) = table_like.get("features").and_then(Item::as_array) {
+        attrs.features = features
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect();
+    }
+    if let Some(default_features) = table_like
+        .get("default-features")
+        .and_then(|v| v.as_value().and_then(|v| v.as_bool()))
+    {
+        attrs.default_features = Some(default_features);
+    }
+    attrs
+}
+
+/// A single recorded dependency occurrence: its manifest path, the original item as written,
+/// the source computed from it, and its `features`/`default-features` attributes.
+type DepOccurrence = (PathBuf, Item, DepSource, DepAttributes);
+
+/// Collect the union of `features` and the common `default-features` setting across every
+/// occurrence of a hoisted dependency, the way `--unify-features` wants them written into
+/// `[workspace.dependencies]`.
+///
+/// `default-features` is `Some(false)` when *every* occurrence disabled it, and `None` when
+/// every occurrence is silent on it (cargo's own default of `true` applies with nothing to
+/// write). When occurrences disagree — some disable it, some don't — this returns
+/// `Some(true)` rather than `None`: cargo only honors a sub-crate's local
+/// `default-features = false` next to `workspace = true` when the workspace entry itself
+/// explicitly sets `default-features = true` first; leaving the key out entirely would cause
+/// cargo to silently ignore the disagreeing sub-crates' local override (and warns that this
+/// will become a hard error).
+fn unify_feature_set(occurrences: &[DepAttributes]) -> (Vec<String>, Option<bool>) {
+    let mut features = Vec::new();
+    for attrs in occurrences {
+        for feature in &attrs.features {
+            if !features.contains(feature) {
+                features.push(feature.clone());
+            }
+        }
+    }
+    let all_disable_default = !occurrences.is_empty()
+        && occurrences
+            .iter()
+            .all(|attrs| attrs.default_features == Some(false));
+    let any_disable_default = occurrences
+        .iter()
+        .any(|attrs| attrs.default_features == Some(false));
+    let default_features = if all_disable_default {
+        Some(false)
+    } else if any_disable_default {
+        Some(true)
+    } else {
+        None
+    };
+    (features, default_features)
+}
+
 /// Update a sub‑crate dependency specification so that it drops its source keys
 /// (version, git, branch, rev, tag, path) and instead marks it as using the workspace source,
 /// while preserving extra attributes (like features, optional, etc.).
-fn update_subcrate_dependency(original: &Item) -> Item {
+///
+/// `unified_features` lists the feature names already written into the workspace entry
+/// (empty unless `--unify-features` hoisted some); any of the dependency's own `features`
+/// not in that set are kept locally as the crate-specific additions the workspace can't see.
+/// `optional` is always re-attached locally since cargo does not allow `optional` to be
+/// inherited from a workspace dependency.
+///
+/// `alias` is the local TOML key this dependency is declared under, and `real_name` is the
+/// actual crate name it was hoisted under (see `dep_package_rename`). When they differ, a
+/// `package = "<real_name>"` rename is written back alongside `workspace = true` so the local
+/// alias keeps resolving to the same crate.
+fn update_subcrate_dependency(
+    original: &Item,
+    unified_features: &[String],
+    alias: &str,
+    real_name: &str,
+) -> Item {
+    let extra_features: Vec<Value> = extract_dep_attributes(original)
+        .features
+        .into_iter()
+        .filter(|f| !unified_features.contains(f))
+        .map(|f| Value::String(Formatted::new(f)))
+        .collect();
+    let rename = (alias != real_name).then(|| real_name.to_string());
+
     match original {
         Item::Table(table) => {
             let mut new_table = table.clone();
@@ -197,21 +504,41 @@ fn update_subcrate_dependency(original: &Item) -> Item {
             new_table.remove("rev");
             new_table.remove("tag");
             new_table.remove("path");
+            new_table.remove("package");
+            new_table.remove("registry");
+            new_table.remove("registry-index");
             // Use the proper boolean syntax.
             new_table["workspace"] = value(true);
+            if let Some(real_name) = &rename {
+                new_table["package"] = value(real_name.clone());
+            }
+            if extra_features.is_empty() {
+                new_table.remove("features");
+            } else {
+                new_table["features"] = value(toml_edit::Array::from_iter(extra_features));
+            }
             debug!("Updated sub-crate dependency table: {:?}", new_table);
             Item::Table(new_table)
         }
         Item::Value(Value::InlineTable(inline)) => {
             let mut new_inline_table = InlineTable::new();
             new_inline_table.insert("workspace", Value::Boolean(Formatted::new(true)));
+            if let Some(real_name) = &rename {
+                new_inline_table.insert("package", Value::String(Formatted::new(real_name.clone())));
+            }
 
             for (key, value) in inline.iter() {
-                if KEYS_TO_IGNORE.contains(&key) {
+                if KEYS_TO_IGNORE.contains(&key) || key == "features" {
                     continue;
                 }
                 new_inline_table.insert(key, value.clone());
             }
+            if !extra_features.is_empty() {
+                new_inline_table.insert(
+                    "features",
+                    Value::Array(toml_edit::Array::from_iter(extra_features)),
+                );
+            }
 
             debug!(
                 "Updated sub-crate dependency inline: {:#?}",
@@ -222,28 +549,313 @@ fn update_subcrate_dependency(original: &Item) -> Item {
         _ => {
             let mut inline = InlineTable::default();
             inline.insert("workspace", Value::Boolean(Formatted::new(true)));
+            if let Some(real_name) = &rename {
+                inline.insert("package", Value::String(Formatted::new(real_name.clone())));
+            }
             debug!("Updated sub-crate dependency inline: {:?}", inline);
             value(inline)
         }
     }
 }
 
+/// Parsed command-line options for `cargo-hoist`.
+struct Args {
+    workspace_dir: PathBuf,
+    /// `--unify-features`: collect the union of `features`/`default-features` across all
+    /// occurrences of a hoisted dependency into `[workspace.dependencies]`, leaving only
+    /// each sub-crate's *extra* features behind.
+    unify_features: bool,
+    /// `--resolve-semver`: try to auto-merge conflicting `DepSource::Version` requirements
+    /// before falling back to the interactive prompt.
+    resolve_semver: bool,
+    /// `--non-interactive`: never prompt on stdin; conflicts that can't be auto-resolved are
+    /// skipped instead.
+    non_interactive: bool,
+    /// `--path-base <NAME>`: emit hoisted path dependencies as `{ base = NAME, path = ... }`
+    /// (RFC 3529) under a single named entry in `[workspace.path-bases]`, instead of inlining
+    /// a workspace-root-relative path into each one.
+    path_base: Option<String>,
+    /// `--config <FILE>`: a `hoist.toml` with include/exclude globs, a minimum-occurrence
+    /// threshold, and per-dependency source overrides (see `HoistConfig`).
+    config: Option<PathBuf>,
+    /// `--dry-run`: compute all intended edits but print a unified diff per manifest instead
+    /// of writing files.
+    dry_run: bool,
+}
+
+/// Parse `cargo-hoist`'s command-line arguments. The workspace directory is the sole
+/// positional argument (defaulting to the current directory); everything else is a flag.
+fn parse_args() -> Result<Args, Box<dyn Error>> {
+    let mut workspace_dir = None;
+    let mut unify_features = false;
+    let mut resolve_semver = false;
+    let mut non_interactive = false;
+    let mut path_base = None;
+    let mut config = None;
+    let mut dry_run = false;
+    let mut args_iter = env::args().skip(1);
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--unify-features" => unify_features = true,
+            "--resolve-semver" => resolve_semver = true,
+            "--non-interactive" => non_interactive = true,
+            "--dry-run" => dry_run = true,
+            "--path-base" => {
+                path_base = Some(
+                    args_iter
+                        .next()
+                        .ok_or("--path-base requires a <NAME> argument")?,
+                );
+            }
+            "--config" => {
+                config = Some(PathBuf::from(
+                    args_iter.next().ok_or("--config requires a <FILE> argument")?,
+                ));
+            }
+            other if other.starts_with("--") => {
+                return Err(format!("Unknown option: {}", other).into());
+            }
+            positional => {
+                if workspace_dir.is_some() {
+                    return Err("Only one workspace directory may be given".into());
+                }
+                workspace_dir = Some(PathBuf::from(positional));
+            }
+        }
+    }
+    Ok(Args {
+        workspace_dir: match workspace_dir {
+            Some(dir) => dir,
+            None => env::current_dir()?,
+        },
+        unify_features,
+        resolve_semver,
+        non_interactive,
+        path_base,
+        config,
+        dry_run,
+    })
+}
+
+/// Default minimum number of crates a dependency must appear in (within the same dependency
+/// table) before a `--config hoist.toml` that omits `min-occurrences` will hoist it. This only
+/// applies once `--config` is in use; without `--config` at all, hoisting keeps its prior
+/// behavior of considering every dependency regardless of occurrence count (see
+/// `NO_CONFIG_MIN_OCCURRENCES`).
+const DEFAULT_MIN_OCCURRENCES: usize = 2;
+
+/// Minimum occurrence threshold used when no `--config` is given at all, preserving the
+/// pre-`--config` behavior of hoisting a dependency even if only one crate declares it.
+const NO_CONFIG_MIN_OCCURRENCES: usize = 1;
+
+/// Rules loaded from a `--config hoist.toml` file: which dependencies are eligible for
+/// hoisting, how many occurrences are required, and explicit source overrides that
+/// short-circuit the interactive conflict prompt.
+#[derive(Debug, Default)]
+struct HoistConfig {
+    /// Glob patterns a dependency name must match to be hoisted. Empty means "match anything".
+    include: Vec<Pattern>,
+    /// Glob patterns that exclude a dependency from hoisting even if `include` matches.
+    exclude: Vec<Pattern>,
+    /// Minimum number of occurrences (within one dependency table) required to hoist.
+    min_occurrences: usize,
+    /// Per-dependency source overrides, keyed by dependency name, that are used as-is instead
+    /// of going through conflict detection.
+    overrides: HashMap<String, DepSource>,
+}
+
+impl HoistConfig {
+    /// Whether `dep_name` is eligible for hoisting under this config's include/exclude globs.
+    fn allows(&self, dep_name: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| p.matches(dep_name));
+        let excluded = self.exclude.iter().any(|p| p.matches(dep_name));
+        included && !excluded
+    }
+}
+
+/// Load a `--config hoist.toml` file.
+fn load_hoist_config(
+    config_path: &Path,
+    workspace_dir: &Path,
+) -> Result<HoistConfig, Box<dyn Error>> {
+    let contents = fs::read_to_string(config_path)
+        .map_err(|e| format!("Could not read {}: {}", config_path.display(), e))?;
+    let doc: DocumentMut = contents
+        .parse()
+        .map_err(|e: TomlError| format!("Could not parse {} as TOML: {}", config_path.display(), e))?;
+
+    let parse_globs = |key: &str| -> Result<Vec<Pattern>, Box<dyn Error>> {
+        let Some(array) = doc.get(key).and_then(Item::as_array) else {
+            return Ok(Vec::new());
+        };
+        array
+            .iter()
+            .map(|v| {
+                let pattern = v
+                    .as_str()
+                    .ok_or_else(|| format!("`{}` entries must be strings", key))?;
+                Pattern::new(pattern)
+                    .map_err(|e| format!("Invalid glob `{}` in `{}`: {}", pattern, key, e).into())
+            })
+            .collect()
+    };
+
+    let min_occurrences = doc
+        .get("min-occurrences")
+        .and_then(|v| v.as_value().and_then(|v| v.as_integer()))
+        .map(|n| n.max(1) as usize)
+        .unwrap_or(DEFAULT_MIN_OCCURRENCES);
+
+    let mut overrides = HashMap::new();
+    if let Some(overrides_table) = doc.get("overrides").and_then(Item::as_table) {
+        // Overrides are parsed the same way a dependency declaration in a Cargo.toml would
+        // be, relative to the workspace root, so they can use `version`, `git`, or `path`.
+        let root_cargo = workspace_dir.join("Cargo.toml");
+        for (dep_name, item) in overrides_table.iter() {
+            let source = compute_dep_source(item, &root_cargo, workspace_dir).ok_or_else(|| {
+                format!("Could not determine an override source for `{}`", dep_name)
+            })?;
+            overrides.insert(dep_name.to_string(), source);
+        }
+    }
+
+    Ok(HoistConfig {
+        include: parse_globs("include")?,
+        exclude: parse_globs("exclude")?,
+        min_occurrences,
+        overrides,
+    })
+}
+
+/// Render a unified diff between a manifest's old and new contents, the way `--dry-run` shows
+/// intended edits without writing them.
+fn print_dry_run_diff(path: &Path, old: &str, new: &str) {
+    if old == new {
+        return;
+    }
+    let diff = TextDiff::from_lines(old, new);
+    print!(
+        "{}",
+        diff.unified_diff()
+            .context_radius(3)
+            .header(&path.display().to_string(), &path.display().to_string())
+    );
+}
+
+/// Compute the longest shared directory-component prefix across a set of relative paths, the
+/// way `--path-base` picks a base directory for `[workspace.path-bases]`. Comparison is
+/// component-wise (not byte-wise) so e.g. `./vendor-foo` and `./vendor/bar` don't spuriously
+/// share a `./vendor` prefix. Returns an empty path if `paths` is empty or shares nothing.
+fn longest_common_dir_prefix(paths: &[&str]) -> PathBuf {
+    let mut component_lists: Vec<Vec<std::path::Component>> =
+        paths.iter().map(|p| Path::new(p).components().collect()).collect();
+    let Some(shortest_len) = component_lists.iter().map(Vec::len).min() else {
+        return PathBuf::new();
+    };
+    let mut common_len = 0;
+    'outer: while common_len < shortest_len {
+        let candidate = component_lists[0][common_len];
+        for components in &component_lists[1..] {
+            if components[common_len] != candidate {
+                break 'outer;
+            }
+        }
+        common_len += 1;
+    }
+    component_lists.truncate(1);
+    component_lists[0].truncate(common_len);
+    component_lists[0].iter().collect()
+}
+
+/// Try to automatically resolve a set of conflicting `DepSource::Version` requirements using
+/// semver semantics, the way `--resolve-semver` does. Returns `Some` only when every option is
+/// a `Version` source using a plain caret requirement, all pointing at the same registry, and
+/// all requirements share the same "significant digit" (major for `>=1.0.0`, major.minor for
+/// `0.x`) — in which case the requirement with the highest lower bound is chosen, since any
+/// crate accepting the loosest of them also accepts the tightest (merging `^1.2` and `^1.4`
+/// yields `^1.4`). Mixed source kinds, differing registries, exact `=` pins, and differing
+/// significant digits are left for the interactive prompt.
+fn try_resolve_semver_conflict(options: &[DepSource]) -> Option<DepSource> {
+    let mut parsed = Vec::with_capacity(options.len());
+    let mut registry = None;
+    let mut registry_index = None;
+    for (i, option) in options.iter().enumerate() {
+        let DepSource::Version {
+            req,
+            registry: this_registry,
+            registry_index: this_registry_index,
+        } = option
+        else {
+            // Mixed source kinds (git/path/version) are always incompatible.
+            return None;
+        };
+        if i == 0 {
+            registry = this_registry.clone();
+            registry_index = this_registry_index.clone();
+        } else if *this_registry != registry || *this_registry_index != registry_index {
+            // Two occurrences pointing at different registries are never auto-merged.
+            return None;
+        }
+        parsed.push((req.as_str(), VersionReq::parse(req).ok()?));
+    }
+
+    let mut significant_digit = None;
+    let mut best: Option<(&str, Version)> = None;
+    for (raw, req) in &parsed {
+        let [comparator] = req.comparators.as_slice() else {
+            return None;
+        };
+        if comparator.op != Op::Caret {
+            return None;
+        }
+        let digit = if comparator.major != 0 {
+            (comparator.major, None)
+        } else {
+            (comparator.major, comparator.minor)
+        };
+        match significant_digit {
+            None => significant_digit = Some(digit),
+            Some(existing) if existing == digit => {}
+            Some(_) => return None,
+        }
+        let lower_bound = Version::new(
+            comparator.major,
+            comparator.minor.unwrap_or(0),
+            comparator.patch.unwrap_or(0),
+        );
+        if best.as_ref().is_none_or(|(_, v)| lower_bound > *v) {
+            best = Some((raw, lower_bound));
+        }
+    }
+
+    best.map(|(raw, _)| DepSource::Version {
+        req: raw.to_string(),
+        registry,
+        registry_index,
+    })
+}
+
 /// A CLI tool (suggested name: `cargo-hoist`) that walks a Rust workspace,
 /// finds shared dependencies (those declared in multiple crates) and “hoists”
 /// — their source information (version, git, or path) into the workspace root’s Cargo.toml under
 /// `[workspace.dependencies]`. For dependencies with local paths, the tool updates the path to be relative
-/// to the workspace root. Extra attributes (such as features, optional, etc.) remain in the sub‑crate manifests.
+/// to the workspace root. Extra attributes (such as features, optional, etc.) remain in the sub‑crate manifests,
+/// unless `--unify-features` is given, in which case the common `features`/`default-features` set is hoisted too.
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::init();
 
-    // Determine the workspace root from the command-line argument or default to "."
-    let workspace_dir = if let Some(arg1) = env::args().nth(1) {
-        PathBuf::from(arg1)
-    } else {
-        env::current_dir()?
-    };
+    let args = parse_args()?;
+    let workspace_dir = args.workspace_dir.clone();
     debug!("Workspace directory is: {:?}", workspace_dir);
 
+    let hoist_config = match &args.config {
+        Some(config_path) => load_hoist_config(config_path, &workspace_dir)?,
+        None => HoistConfig {
+            min_occurrences: NO_CONFIG_MIN_OCCURRENCES,
+            ..Default::default()
+        },
+    };
+
     // Read and parse the root Cargo.toml
     let root_cargo = workspace_dir.join("Cargo.toml");
     debug!("Reading root Cargo.toml at: {:?}", root_cargo);
@@ -280,12 +892,16 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    // Map each dependency name to a vector of occurrences.
-    // Each occurrence is (path-to-Cargo.toml, dependency specification, computed DepSource).
-    let mut dep_occurrences: HashMap<String, Vec<(PathBuf, Item, DepSource)>> = HashMap::new();
+    // Map each (table, dependency name) pair to a vector of occurrences. Each occurrence is
+    // (path-to-Cargo.toml, dependency specification, computed DepSource, its
+    // features/default-features attributes). Keying by table as well as name keeps e.g. a
+    // crate used as both a normal and a dev-dependency from being merged into one occurrence.
+    let mut dep_occurrences: HashMap<(DepTableKey, String), Vec<DepOccurrence>> = HashMap::new();
 
-    // First pass: read each package Cargo.toml and record its [dependencies].
-    // **Skip any dependency that is already a workspace import.**
+    // First pass: read each package Cargo.toml and record its dependency tables —
+    // [dependencies], [dev-dependencies], [build-dependencies], and their target-scoped
+    // variants under [target.'cfg(...)'.*]. **Skip any dependency that is already a workspace
+    // import.**
     for member_path in &member_paths {
         debug!("Processing member {:?}", member_path);
         let contents = fs::read_to_string(member_path)
@@ -297,9 +913,10 @@ fn main() -> Result<(), Box<dyn Error>> {
                 e.to_string()
             )
         })?;
-        if let Some(deps) = doc.get("dependencies").and_then(Item::as_table) {
+        for table_key in discover_dep_tables(&doc) {
+            let deps = get_dep_table(&doc, &table_key).expect("table_key was just discovered");
             for (dep_name, dep_value) in deps.iter() {
-                debug!("Checking dependency `{}`", dep_name);
+                debug!("Checking dependency `{}` in {}", dep_name, table_key);
                 // Skip dependencies that already use the workspace import.
                 if let Some(table) = dep_value.as_table() {
                     if table
@@ -323,10 +940,16 @@ fn main() -> Result<(), Box<dyn Error>> {
                         );
                         continue;
                     };
+                    // Group by the real crate name (honoring `package = "..."` renames) so a
+                    // renamed dependency dedups against other occurrences of the same crate
+                    // rather than colliding with an unrelated dependency sharing its alias.
+                    let real_name =
+                        dep_package_rename(dep_value).unwrap_or_else(|| dep_name.to_string());
+                    let attrs = extract_dep_attributes(dep_value);
                     dep_occurrences
-                        .entry(dep_name.to_string())
+                        .entry((table_key.clone(), real_name))
                         .or_default()
-                        .push((member_path.clone(), dep_value.clone(), dep_source));
+                        .push((member_path.clone(), dep_value.clone(), dep_source, attrs));
                 } else {
                     warn!(
                         "Warning: Could not determine source for dependency `{}` in {}. Skipping.",
@@ -338,18 +961,49 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    // Determine shared dependencies: those that appear in more than one package.
-    // For each, if there are conflicting source specifications, ask the user to choose one.
-    let mut shared_deps: HashMap<String, DepSource> = HashMap::new();
-    for (dep_name, occurrences) in dep_occurrences {
+    // Determine shared dependencies: those that appear in at least `min_occurrences` packages
+    // (within the same dependency table; 1 when no `--config` is given). For each, if there are conflicting source specifications, ask
+    // the user to choose one.
+    let mut shared_deps: HashMap<(DepTableKey, String), DepSource> = HashMap::new();
+    // When `--unify-features` is set, the union feature set chosen for each hoisted dependency.
+    let mut unified_features: HashMap<(DepTableKey, String), (Vec<String>, Option<bool>)> =
+        HashMap::new();
+    for ((table_key, dep_name), occurrences) in dep_occurrences {
         debug!(
-            "Dependency `{}` appears in {} members",
+            "Dependency `{}` in {} appears in {} members",
             dep_name,
+            table_key,
             occurrences.len()
         );
+        if !hoist_config.allows(&dep_name) {
+            debug!("Skipping `{}` (excluded by --config include/exclude)", dep_name);
+            continue;
+        }
+        if occurrences.len() < hoist_config.min_occurrences {
+            debug!(
+                "Skipping `{}` ({} occurrence(s) below the configured minimum of {})",
+                dep_name,
+                occurrences.len(),
+                hoist_config.min_occurrences
+            );
+            continue;
+        }
+        if let Some(override_source) = hoist_config.overrides.get(&dep_name) {
+            debug!("Using configured override source for `{}`", dep_name);
+            if args.unify_features {
+                let attrs: Vec<DepAttributes> =
+                    occurrences.iter().map(|(_, _, _, a)| a.clone()).collect();
+                unified_features.insert(
+                    (table_key.clone(), dep_name.clone()),
+                    unify_feature_set(&attrs),
+                );
+            }
+            shared_deps.insert((table_key, dep_name), override_source.clone());
+            continue;
+        }
         // Collect unique DepSource values.
         let mut source_options: Vec<DepSource> = Vec::new();
-        for (_, _, dep_source) in &occurrences {
+        for (_, _, dep_source, _) in &occurrences {
             if !source_options.contains(dep_source) {
                 source_options.push(dep_source.clone());
             }
@@ -357,9 +1011,26 @@ fn main() -> Result<(), Box<dyn Error>> {
         if source_options.is_empty() {
             continue;
         }
-        if source_options.len() == 1 {
+        let semver_merge = if args.resolve_semver {
+            try_resolve_semver_conflict(&source_options)
+        } else {
+            None
+        };
+        let chosen_source = if source_options.len() == 1 {
             // All occurrences agree.
-            shared_deps.insert(dep_name, source_options[0].clone());
+            Some(source_options[0].clone())
+        } else if let Some(merged) = semver_merge {
+            debug!(
+                "Auto-resolved conflicting versions for `{}` via semver merge",
+                dep_name
+            );
+            Some(merged)
+        } else if args.non_interactive {
+            println!(
+                "Dependency `{}` has conflicting source specifications; skipping (non-interactive)",
+                dep_name
+            );
+            None
         } else {
             // Conflicting sources found. Ask the user to choose one.
             println!(
@@ -382,11 +1053,69 @@ fn main() -> Result<(), Box<dyn Error>> {
             };
             if choice == 0 || choice > source_options.len() {
                 debug!("Skipping hoisting dependency `{}`", dep_name);
+                None
             } else {
-                shared_deps.insert(dep_name, source_options[choice - 1].clone());
+                Some(source_options[choice - 1].clone())
+            }
+        };
+        if let Some(chosen_source) = chosen_source {
+            if args.unify_features {
+                let attrs: Vec<DepAttributes> =
+                    occurrences.iter().map(|(_, _, _, a)| a.clone()).collect();
+                unified_features.insert(
+                    (table_key.clone(), dep_name.clone()),
+                    unify_feature_set(&attrs),
+                );
             }
+            shared_deps.insert((table_key, dep_name), chosen_source);
+        }
+    }
+
+    // `[workspace.dependencies]` has no notion of dependency kind, so a name hoisted from more
+    // than one dependency table (e.g. both [dependencies] and [dev-dependencies] across members)
+    // can only get a single entry. That's safe when every table kind resolved to the same
+    // source and unified features — but if they disagree, writing one of them would silently
+    // overwrite the others with whichever table-kind `shared_deps` (a HashMap, so unordered)
+    // happens to iterate first. Detect that disagreement and refuse to hoist the name at all,
+    // leaving every occurrence in place, rather than emit a nondeterministic one-of-N manifest.
+    let mut table_keys_by_name: HashMap<&str, Vec<&DepTableKey>> = HashMap::new();
+    for (table_key, dep_name) in shared_deps.keys() {
+        table_keys_by_name
+            .entry(dep_name.as_str())
+            .or_default()
+            .push(table_key);
+    }
+    let mut blocked_names: HashSet<String> = HashSet::new();
+    for (dep_name, table_keys) in &table_keys_by_name {
+        let Some((first_key, rest)) = table_keys.split_first() else {
+            continue;
+        };
+        if rest.is_empty() {
+            continue;
+        }
+        let resolution = |table_key: &DepTableKey| {
+            (
+                shared_deps[&(table_key.clone(), dep_name.to_string())].clone(),
+                unified_features
+                    .get(&(table_key.clone(), dep_name.to_string()))
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+        };
+        let first_resolution = resolution(first_key);
+        if rest.iter().any(|key| resolution(key) != first_resolution) {
+            warn!(
+                "Dependency `{}` resolves differently across dependency tables (e.g. \
+                 [dependencies] vs [dev-dependencies]); [workspace.dependencies] can only hold \
+                 one entry per name, so skipping hoisting `{}` entirely rather than silently \
+                 picking one table's resolution over another",
+                dep_name, dep_name
+            );
+            blocked_names.insert(dep_name.to_string());
         }
     }
+    shared_deps.retain(|(_, dep_name), _| !blocked_names.contains(dep_name));
+    unified_features.retain(|(_, dep_name), _| !blocked_names.contains(dep_name));
 
     // --- Update the workspace root Cargo.toml ---
     // Ensure that [workspace] and [workspace.dependencies] exist.
@@ -400,15 +1129,76 @@ fn main() -> Result<(), Box<dyn Error>> {
     {
         root_doc["workspace"]["dependencies"] = Item::Table(Table::new());
     }
+
+    // If `--path-base` is set, pick a single base directory shared by every hoisted path
+    // dependency and compute each one's path relative to it.
+    let mut path_base_remainders: HashMap<String, String> = HashMap::new();
+    if let Some(base_name) = &args.path_base {
+        let path_deps: Vec<&str> = shared_deps
+            .values()
+            .filter_map(|source| match source {
+                DepSource::Path(p) => Some(p.as_str()),
+                _ => None,
+            })
+            .collect();
+        if !path_deps.is_empty() {
+            let base_dir = longest_common_dir_prefix(&path_deps);
+            for (dep_name, dep_source) in shared_deps.iter().map(|((_, n), s)| (n, s)) {
+                if let DepSource::Path(full_path) = dep_source {
+                    let remainder = diff_paths(full_path, &base_dir)
+                        .unwrap_or_else(|| PathBuf::from(full_path));
+                    let remainder_str = remainder.to_string_lossy().to_string();
+                    // `diff_paths` yields an empty string when `full_path` *is* `base_dir`
+                    // (e.g. only one distinct path dep was hoisted); "." is the valid spelling.
+                    let remainder_str = if remainder_str.is_empty() {
+                        ".".to_string()
+                    } else {
+                        remainder_str
+                    };
+                    path_base_remainders.insert(dep_name.clone(), remainder_str);
+                }
+            }
+            if !root_doc["workspace"]
+                .as_table()
+                .unwrap()
+                .contains_key("path-bases")
+            {
+                root_doc["workspace"]["path-bases"] = Item::Table(Table::new());
+            }
+            root_doc["workspace"]["path-bases"][base_name.as_str()] =
+                value(base_dir.to_string_lossy().to_string());
+            debug!(
+                "Declared path-base `{}` = `{}` in {}",
+                base_name,
+                base_dir.display(),
+                root_cargo.display()
+            );
+        }
+    }
+
     let workspace_deps = root_doc["workspace"]["dependencies"]
         .as_table_mut()
         .unwrap();
 
-    // For each shared dependency, add it (with only its source information) to the workspace dependencies
-    // if not already present.
-    for (dep_name, dep_source) in &shared_deps {
+    // For each shared dependency, add it (with its source information, plus any unified
+    // `features`/`default-features` under `--unify-features`) to the workspace
+    // dependencies if not already present. `[workspace.dependencies]` has no notion of
+    // dependency kind, so a dependency hoisted from e.g. both [dependencies] and
+    // [dev-dependencies] across crates gets a single entry; by this point `shared_deps` only
+    // contains names whose table kinds all agreed on a resolution (see the collision check
+    // above), so which one is iterated first no longer matters.
+    for ((table_key, dep_name), dep_source) in &shared_deps {
         if !workspace_deps.contains_key(dep_name) {
-            let workspace_item = build_workspace_dep(dep_source);
+            let (features, default_features) = unified_features
+                .get(&(table_key.clone(), dep_name.clone()))
+                .cloned()
+                .unwrap_or_default();
+            let path_base = args
+                .path_base
+                .as_deref()
+                .zip(path_base_remainders.get(dep_name).map(String::as_str));
+            let workspace_item =
+                build_workspace_dep(dep_source, &features, default_features, path_base);
             workspace_deps[dep_name] = workspace_item;
             debug!(
                 "Added shared dependency `{}` to workspace.dependencies in {}",
@@ -433,11 +1223,17 @@ fn main() -> Result<(), Box<dyn Error>> {
             )
         })?;
         let mut modified = false;
-        if let Some(deps) = doc.get_mut("dependencies").and_then(Item::as_table_mut) {
+        for table_key in discover_dep_tables(&doc) {
+            let Some(deps) = get_dep_table_mut(&mut doc, &table_key) else {
+                continue;
+            };
             // Collect the keys before mutating.
             let keys: Vec<String> = deps.iter().map(|(k, _)| k.to_string()).collect();
             for key in keys {
-                if shared_deps.contains_key(&key) {
+                let real_name =
+                    dep_package_rename(&deps[&key]).unwrap_or_else(|| key.clone());
+                let shared_key = (table_key.clone(), real_name.clone());
+                if shared_deps.contains_key(&shared_key) {
                     // If the dependency already has a workspace import, skip updating.
                     if let Some(existing) = deps.get(&key) {
                         if let Some(tbl) = existing.as_table() {
@@ -446,33 +1242,171 @@ fn main() -> Result<(), Box<dyn Error>> {
                                 .and_then(|ws| ws.as_value().and_then(|v| v.as_bool()))
                                 == Some(true)
                             {
-                                debug!("Skipping {} in {:?} (already workspace)", key, member_path);
+                                debug!(
+                                    "Skipping {} in {} of {:?} (already workspace)",
+                                    key, table_key, member_path
+                                );
                                 continue;
                             }
                         }
                     }
                     let original = deps[&key].clone();
-                    let new_item = update_subcrate_dependency(&original);
+                    let unified = unified_features
+                        .get(&shared_key)
+                        .map(|(f, _)| f.as_slice())
+                        .unwrap_or(&[]);
+                    let new_item = update_subcrate_dependency(&original, unified, &key, &real_name);
                     deps[&key] = new_item;
                     modified = true;
-                    debug!("Updated dependency `{}` in {:?}", key, member_path);
+                    debug!(
+                        "Updated dependency `{}` (real name `{}`) in {} of {:?}",
+                        key, real_name, table_key, member_path
+                    );
                 }
             }
         }
         if modified {
-            fs::write(member_path, doc.to_string())
-                .map_err(|e| format!("Failed to write {}: {}", member_path.display(), e))?;
-            debug!("Written updated file for {:?}", member_path);
+            let new_contents = doc.to_string();
+            if args.dry_run {
+                print_dry_run_diff(member_path, &contents, &new_contents);
+            } else {
+                fs::write(member_path, new_contents)
+                    .map_err(|e| format!("Failed to write {}: {}", member_path.display(), e))?;
+                debug!("Written updated file for {:?}", member_path);
+            }
         }
     }
 
     // Write the updated workspace root Cargo.toml.
-    fs::write(&root_cargo, root_doc.to_string())
-        .map_err(|e| format!("Failed to write {}: {}", root_cargo.display(), e))?;
-    debug!(
-        "Updated workspace root Cargo.toml at {}",
-        root_cargo.display()
-    );
+    let new_root_contents = root_doc.to_string();
+    if args.dry_run {
+        print_dry_run_diff(&root_cargo, &root_contents, &new_root_contents);
+    } else {
+        fs::write(&root_cargo, new_root_contents)
+            .map_err(|e| format!("Failed to write {}: {}", root_cargo.display(), e))?;
+        debug!(
+            "Updated workspace root Cargo.toml at {}",
+            root_cargo.display()
+        );
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs(features: &[&str], default_features: Option<bool>) -> DepAttributes {
+        DepAttributes {
+            features: features.iter().map(|s| s.to_string()).collect(),
+            default_features,
+        }
+    }
+
+    #[test]
+    fn unify_feature_set_unions_features_and_dedups() {
+        let (features, _) = unify_feature_set(&[
+            attrs(&["a", "b"], None),
+            attrs(&["b", "c"], None),
+        ]);
+        assert_eq!(features, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn unify_feature_set_all_disabled_hoists_false() {
+        let (_, default_features) =
+            unify_feature_set(&[attrs(&[], Some(false)), attrs(&[], Some(false))]);
+        assert_eq!(default_features, Some(false));
+    }
+
+    #[test]
+    fn unify_feature_set_all_silent_hoists_nothing() {
+        let (_, default_features) = unify_feature_set(&[attrs(&[], None), attrs(&[], None)]);
+        assert_eq!(default_features, None);
+    }
+
+    #[test]
+    fn unify_feature_set_partial_disagreement_hoists_true() {
+        // One occurrence disables default features while another doesn't: the workspace entry
+        // must explicitly enable them so the disagreeing occurrence's local override remains
+        // effective (cargo ignores a local override next to an implicit `workspace = true`).
+        let (_, default_features) =
+            unify_feature_set(&[attrs(&[], Some(false)), attrs(&[], None)]);
+        assert_eq!(default_features, Some(true));
+    }
+
+    fn version(req: &str) -> DepSource {
+        DepSource::Version {
+            req: req.to_string(),
+            registry: None,
+            registry_index: None,
+        }
+    }
+
+    #[test]
+    fn resolve_semver_picks_highest_lower_bound() {
+        let resolved = try_resolve_semver_conflict(&[version("^1.2.0"), version("^1.4.0")]);
+        assert_eq!(resolved, Some(version("^1.4.0")));
+    }
+
+    #[test]
+    fn resolve_semver_rejects_differing_significant_digit() {
+        // `^0.x` and `^1.x` have different significant digits (major.minor vs. major alone),
+        // so the two requirements accept disjoint release lines and neither can replace the other.
+        assert_eq!(
+            try_resolve_semver_conflict(&[version("^0.3.0"), version("^1.0.0")]),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_semver_rejects_exact_pins() {
+        assert_eq!(
+            try_resolve_semver_conflict(&[version("=1.2.0"), version("^1.2.0")]),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_semver_rejects_differing_registries() {
+        let a = DepSource::Version {
+            req: "^1.0.0".to_string(),
+            registry: Some("private".to_string()),
+            registry_index: None,
+        };
+        let b = version("^1.2.0");
+        assert_eq!(try_resolve_semver_conflict(&[a, b]), None);
+    }
+
+    #[test]
+    fn resolve_semver_rejects_mixed_source_kinds() {
+        let git = DepSource::Git {
+            url: "https://example.com/repo.git".to_string(),
+            branch: None,
+            rev: None,
+            tag: None,
+        };
+        assert_eq!(try_resolve_semver_conflict(&[version("^1.0.0"), git]), None);
+    }
+
+    #[test]
+    fn longest_common_dir_prefix_component_wise() {
+        // A naive string-prefix comparison would treat "foo" as a prefix of "foobar" here;
+        // comparing path components instead correctly finds no shared directory.
+        let prefix = longest_common_dir_prefix(&["foo/bar", "foobar/baz"]);
+        assert_eq!(prefix, PathBuf::new());
+    }
+
+    #[test]
+    fn longest_common_dir_prefix_shared_ancestor() {
+        let prefix = longest_common_dir_prefix(&["libs/crate-a", "libs/crate-b/sub"]);
+        assert_eq!(prefix, PathBuf::from("libs"));
+    }
+
+    #[test]
+    fn longest_common_dir_prefix_single_path_is_itself() {
+        let prefix = longest_common_dir_prefix(&["libs/crate-a"]);
+        assert_eq!(prefix, PathBuf::from("libs/crate-a"));
+    }
+}